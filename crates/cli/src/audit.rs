@@ -0,0 +1,256 @@
+use crate::util::{cargo_metadata_resolved, CommandResult, Executable};
+use cargo_metadata::{Metadata, Package, PackageId};
+use clap::{ArgEnum, Args};
+use serde::Serialize;
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+#[derive(Args, Debug, Default)]
+/// audit `unsafe` usage across the addon and its dependency tree
+pub struct AuditCommand {
+  /// Path to the `Cargo.toml` manifest
+  #[clap(long, parse(from_os_str))]
+  cwd: Option<PathBuf>,
+
+  /// Also scan each crate's test sources
+  #[clap(long)]
+  include_tests: bool,
+
+  /// Output format
+  #[clap(long, arg_enum, default_value = "text")]
+  format: AuditFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum AuditFormat {
+  Text,
+  Json,
+}
+
+impl Default for AuditFormat {
+  fn default() -> Self {
+    AuditFormat::Text
+  }
+}
+
+#[derive(Serialize)]
+struct CrateAudit {
+  name: String,
+  version: String,
+  forbids_unsafe: bool,
+  unsafe_count: usize,
+}
+
+impl Executable for AuditCommand {
+  fn execute(&mut self) -> CommandResult {
+    let metadata = cargo_metadata_resolved(self.cwd.as_deref());
+
+    let audits: HashMap<&PackageId, CrateAudit> = metadata
+      .packages
+      .iter()
+      .map(|package| (&package.id, self.audit_package(package)))
+      .collect();
+
+    match self.format {
+      AuditFormat::Json => {
+        let report: Vec<&CrateAudit> = audits.values().collect();
+        println!(
+          "{}",
+          serde_json::to_string_pretty(&report).expect("failed to serialize audit report")
+        );
+      }
+      AuditFormat::Text => print_tree(&metadata, &audits),
+    }
+
+    Ok(())
+  }
+}
+
+impl AuditCommand {
+  fn audit_package(&self, package: &Package) -> CrateAudit {
+    let root = package
+      .manifest_path
+      .parent()
+      .map(|path| path.as_std_path().to_path_buf())
+      .unwrap_or_default();
+
+    let mut unsafe_count = 0;
+
+    for source in collect_rust_sources(&root, self.include_tests) {
+      let Ok(contents) = fs::read_to_string(&source) else {
+        continue;
+      };
+
+      unsafe_count += count_unsafe_usages(&contents);
+    }
+
+    let forbids_unsafe = crate_root_sources(package).into_iter().any(|source| {
+      fs::read_to_string(source)
+        .map(|contents| contents.contains("#![forbid(unsafe_code)]"))
+        .unwrap_or(false)
+    });
+
+    CrateAudit {
+      name: package.name.clone(),
+      version: package.version.to_string(),
+      forbids_unsafe,
+      unsafe_count,
+    }
+  }
+}
+
+/// Returns the crate-root source files (one per `lib`/`cdylib`/`proc-macro`/`bin` target) where a
+/// crate-level `#![forbid(unsafe_code)]` attribute could live.
+fn crate_root_sources(package: &Package) -> Vec<PathBuf> {
+  package
+    .targets
+    .iter()
+    .filter(|target| {
+      target
+        .kind
+        .iter()
+        .any(|kind| kind == "lib" || kind == "bin" || kind == "cdylib" || kind == "proc-macro")
+    })
+    .map(|target| target.src_path.as_std_path().to_path_buf())
+    .collect()
+}
+
+/// Walks `root` for `.rs` files, skipping `target/` build output and, unless `include_tests`
+/// is set, the crate's `tests/`/`benches/` directories.
+fn collect_rust_sources(root: &Path, include_tests: bool) -> Vec<PathBuf> {
+  let mut sources = Vec::new();
+  let mut dirs = vec![root.to_path_buf()];
+
+  while let Some(dir) = dirs.pop() {
+    let Ok(entries) = fs::read_dir(&dir) else {
+      continue;
+    };
+
+    for entry in entries.flatten() {
+      let path = entry.path();
+      let name = entry.file_name();
+      let name = name.to_string_lossy();
+
+      if path.is_dir() {
+        if name == "target" || name == ".git" {
+          continue;
+        }
+        if !include_tests && (name == "tests" || name == "benches") {
+          continue;
+        }
+        dirs.push(path);
+      } else if path.extension().and_then(|ext| ext.to_str()) == Some("rs") {
+        sources.push(path);
+      }
+    }
+  }
+
+  sources
+}
+
+/// Counts occurrences of the `unsafe` keyword (blocks, fns, impls, traits) in `contents`.
+///
+/// This is a lexical heuristic, not a full parse: it matches the whole word `unsafe` and does
+/// not attempt to skip occurrences inside comments or string literals.
+fn count_unsafe_usages(contents: &str) -> usize {
+  let bytes = contents.as_bytes();
+  let needle = b"unsafe";
+  let mut count = 0;
+  let mut index = 0;
+
+  while let Some(found) = find_subslice(&bytes[index..], needle) {
+    let start = index + found;
+    let end = start + needle.len();
+
+    let preceded_by_boundary = start == 0 || !is_word_byte(bytes[start - 1]);
+    let followed_by_boundary = end == bytes.len() || !is_word_byte(bytes[end]);
+
+    if preceded_by_boundary && followed_by_boundary {
+      count += 1;
+    }
+
+    index = end;
+  }
+
+  count
+}
+
+fn is_word_byte(byte: u8) -> bool {
+  byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
+
+/// Prints the dependency tree rooted at the workspace's packages, annotating each crate with
+/// its `unsafe` usage and `#![forbid(unsafe_code)]` status.
+fn print_tree(metadata: &Metadata, audits: &HashMap<&PackageId, CrateAudit>) {
+  let Some(resolve) = &metadata.resolve else {
+    return;
+  };
+
+  let deps_by_id: HashMap<&PackageId, Vec<&PackageId>> = resolve
+    .nodes
+    .iter()
+    .map(|node| {
+      (
+        &node.id,
+        node.dependencies.iter().collect::<Vec<&PackageId>>(),
+      )
+    })
+    .collect();
+
+  let mut seen_roots = std::collections::HashSet::new();
+  let roots: Vec<&PackageId> = resolve
+    .root
+    .iter()
+    .chain(metadata.workspace_members.iter())
+    .filter(|id| seen_roots.insert(*id))
+    .collect();
+
+  let mut visited = std::collections::HashSet::new();
+  for root in roots {
+    print_node(root, &deps_by_id, audits, 0, &mut visited);
+  }
+}
+
+fn print_node<'a>(
+  id: &'a PackageId,
+  deps_by_id: &HashMap<&'a PackageId, Vec<&'a PackageId>>,
+  audits: &HashMap<&PackageId, CrateAudit>,
+  depth: usize,
+  visited: &mut std::collections::HashSet<&'a PackageId>,
+) {
+  let indent = "  ".repeat(depth);
+
+  match audits.get(id) {
+    Some(audit) => {
+      let forbid_status = if audit.forbids_unsafe {
+        "forbids unsafe"
+      } else {
+        "allows unsafe"
+      };
+      println!(
+        "{indent}{} {} [{forbid_status}, {} unsafe usage(s)]",
+        audit.name, audit.version, audit.unsafe_count
+      );
+    }
+    None => println!("{indent}{}", id.repr),
+  }
+
+  if !visited.insert(id) {
+    return;
+  }
+
+  if let Some(deps) = deps_by_id.get(id) {
+    for dep in deps {
+      print_node(dep, deps_by_id, audits, depth + 1, visited);
+    }
+  }
+}
@@ -1,10 +1,19 @@
-use crate::util::{get_system_default_target, CommandResult, Executable};
+use crate::util::{
+  cargo_metadata, get_system_default_target, resolve_addon_package, CommandResult, Executable,
+};
 use clap::Args;
 use clap_cargo::{Features, Workspace};
 use log::trace;
 use rand::{thread_rng, RngCore};
 use std::fmt::Write;
-use std::{env::temp_dir, path::PathBuf, process::Command};
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::{
+  env::temp_dir,
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
 
 #[derive(Args, Debug, Default)]
 /// build the napi-rs crates
@@ -78,18 +87,32 @@ impl Executable for BuildCommand {
       log::set_max_level(log::LevelFilter::Trace)
     }
 
-    self.run();
-
-    Ok(())
+    self.run()
   }
 }
 
 impl BuildCommand {
-  fn run(&mut self) {
+  fn run(&mut self) -> CommandResult {
+    let metadata = cargo_metadata(self.cwd.as_deref());
+    let package = resolve_addon_package(&metadata, &self.workspace.package)?;
+    let addon_name = package.name.clone();
+    let package_id = package.id.repr.clone();
+
+    if self.workspace.package.is_empty() && !self.workspace.all && !self.workspace.workspace {
+      trace!("auto-selected package `{}`", addon_name);
+      self.workspace.package = vec![addon_name.clone()];
+    }
+
+    if self.dest.is_none() {
+      if let Some(manifest_dir) = package.manifest_path.parent() {
+        self.dest = Some(manifest_dir.as_std_path().to_path_buf());
+      }
+    }
+
     self.intermediate_type_file = get_intermediate_type_file();
 
     let mut cmd = Command::new("cargo");
-    cmd.arg("build");
+    cmd.arg("build").arg("--message-format=json-render-diagnostics");
 
     self
       .set_cwd(&mut cmd)
@@ -99,7 +122,114 @@ impl BuildCommand {
       .set_envs(&mut cmd)
       .set_bypass_args(&mut cmd);
 
-    cmd.spawn().expect("failed to execute `cargo build`");
+    cmd.stdout(Stdio::piped());
+
+    let mut child = cmd.spawn().expect("failed to execute `cargo build`");
+    let stdout = child.stdout.take().expect("failed to capture cargo stdout");
+
+    let mut artifact = None;
+    for line in BufReader::new(stdout).lines() {
+      let line = match line {
+        Ok(line) => line,
+        Err(_) => continue,
+      };
+      if let Some(path) = parse_cdylib_artifact(&line, &package_id) {
+        artifact = Some(path);
+      }
+    }
+
+    let status = child.wait().expect("failed to wait on `cargo build`");
+    if !status.success() {
+      std::process::exit(status.code().unwrap_or(1));
+    }
+
+    match artifact {
+      Some(artifact) => self.install_artifact(&artifact, &addon_name),
+      None => log::warn!("`cargo build` did not produce a `cdylib` artifact"),
+    }
+
+    self.run_pipe(&self.js_binding_paths(&addon_name));
+
+    Ok(())
+  }
+
+  /// Paths to the generated JS/TS binding files, if binding generation is enabled.
+  fn js_binding_paths(&self, name: &str) -> Vec<PathBuf> {
+    if self.disable_js_binding {
+      return vec![];
+    }
+
+    let dest = self.dest.clone().unwrap_or_else(|| PathBuf::from("."));
+    let js_path = self
+      .js_binding
+      .clone()
+      .unwrap_or_else(|| dest.join(format!("{name}.js")));
+    let dts_path = js_path.with_extension("d.ts");
+
+    [js_path, dts_path]
+      .into_iter()
+      .filter(|path| path.exists())
+      .collect()
+  }
+
+  /// Runs the `--pipe` command once, with every generated binding file path appended as a
+  /// trailing argument, e.g. `--pipe="prettier -w"` runs `prettier -w <js> <d.ts>`.
+  fn run_pipe(&self, files: &[PathBuf]) {
+    let Some(pipe) = &self.pipe else {
+      return;
+    };
+    if files.is_empty() {
+      return;
+    }
+
+    let mut parts = pipe.split_whitespace();
+    let program = parts.next().expect("`--pipe` must not be empty");
+
+    trace!("piping {:?} through `{}`", files, pipe);
+    let status = Command::new(program)
+      .args(parts)
+      .args(files)
+      .status()
+      .expect("failed to execute `--pipe` command");
+
+    if !status.success() {
+      std::process::exit(status.code().unwrap_or(1));
+    }
+  }
+
+  fn install_artifact(&self, artifact: &Path, name: &str) {
+    let dest = self.dest.clone().unwrap_or_else(|| PathBuf::from("."));
+    fs::create_dir_all(&dest).unwrap_or_else(|err| {
+      panic!("failed to create dest dir {}: {}", dest.display(), err)
+    });
+
+    let target = self.target.as_deref().unwrap_or("");
+    let dest_file = dest.join(format!("{name}.{target}.node"));
+
+    trace!(
+      "installing {} to {}",
+      artifact.display(),
+      dest_file.display()
+    );
+    fs::copy(artifact, &dest_file).unwrap_or_else(|err| {
+      panic!(
+        "failed to copy {} to {}: {}",
+        artifact.display(),
+        dest_file.display(),
+        err
+      )
+    });
+
+    if self.strip {
+      trace!("stripping {}", dest_file.display());
+      let status = Command::new("strip")
+        .arg(&dest_file)
+        .status()
+        .expect("failed to execute `strip`");
+      if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+      }
+    }
   }
 
   fn set_cwd(&mut self, cmd: &mut Command) -> &mut Self {
@@ -204,3 +334,32 @@ fn get_intermediate_type_file() -> PathBuf {
 
   temp_dir().join(format!("type_def.{hex_string}.tmp"))
 }
+
+/// Parses a single line of cargo's `--message-format=json-render-diagnostics`
+/// output, returning the path to the `cdylib` artifact if this line is a
+/// `compiler-artifact` message for one, and its `package_id` matches `package_id`.
+pub(crate) fn parse_cdylib_artifact(line: &str, package_id: &str) -> Option<PathBuf> {
+  let message: serde_json::Value = serde_json::from_str(line).ok()?;
+
+  if message["reason"].as_str()? != "compiler-artifact" {
+    return None;
+  }
+
+  if message["package_id"].as_str()? != package_id {
+    return None;
+  }
+
+  let is_cdylib = message["target"]["kind"]
+    .as_array()?
+    .iter()
+    .any(|kind| kind == "cdylib");
+  if !is_cdylib {
+    return None;
+  }
+
+  message["filenames"]
+    .as_array()?
+    .iter()
+    .find_map(|filename| filename.as_str())
+    .map(PathBuf::from)
+}
@@ -0,0 +1,209 @@
+use crate::build::parse_cdylib_artifact;
+use crate::util::{
+  cargo_metadata, get_system_default_target, resolve_addon_package, CommandResult, Executable,
+};
+use clap::Args;
+use clap_cargo::{Features, Workspace};
+use log::trace;
+use std::io::{BufRead, BufReader};
+use std::{
+  env::temp_dir,
+  path::PathBuf,
+  process::{Command, Stdio},
+};
+
+#[derive(Args, Debug, Default)]
+/// build the napi-rs crate with coverage instrumentation, run a test command that exercises
+/// the resulting addon, and write an lcov report of the Rust code it executed (JS coverage is
+/// not collected or merged)
+pub struct CoverageCommand {
+  /// Build for the target triple, bypassed to `cargo build --target`
+  #[clap(short, long)]
+  target: Option<String>,
+
+  /// Path to the `Cargo.toml` manifest
+  #[clap(long, parse(from_os_str))]
+  cwd: Option<PathBuf>,
+
+  /// Command that exercises the instrumented addon, e.g. `--test-command "node --test"`
+  #[clap(long)]
+  test_command: String,
+
+  /// Path to write the merged lcov report to
+  #[clap(long, parse(from_os_str), default_value = "coverage.lcov")]
+  output: PathBuf,
+
+  /// Exit with code 0 even if `--test-command` fails, as long as the report was generated
+  #[clap(long)]
+  ignore_run_fail: bool,
+
+  /// Verbosely log coverage command trace
+  #[clap(short, long)]
+  verbose: bool,
+
+  #[clap(flatten)]
+  features: Features,
+
+  #[clap(flatten)]
+  workspace: Workspace,
+}
+
+impl Executable for CoverageCommand {
+  fn execute(&mut self) -> CommandResult {
+    if self.verbose {
+      log::set_max_level(log::LevelFilter::Trace)
+    }
+
+    self.run()
+  }
+}
+
+impl CoverageCommand {
+  fn run(&mut self) -> CommandResult {
+    let profraw_dir = temp_dir().join(format!("napi-coverage-{}", std::process::id()));
+    std::fs::create_dir_all(&profraw_dir).expect("failed to create coverage temp dir");
+
+    let artifact = self.build_instrumented(&profraw_dir);
+
+    trace!("running test command: {}", self.test_command);
+    let test_status = self
+      .test_command_parts(&profraw_dir)
+      .status()
+      .expect("failed to execute `--test-command`");
+
+    let merged_profdata = profraw_dir.join("merged.profdata");
+    merge_profiles(&profraw_dir, &merged_profdata);
+    export_lcov(&merged_profdata, &artifact, &self.output);
+
+    if !test_status.success() && !self.ignore_run_fail {
+      std::process::exit(test_status.code().unwrap_or(1));
+    }
+
+    Ok(())
+  }
+
+  fn build_instrumented(&mut self, profraw_dir: &PathBuf) -> PathBuf {
+    let metadata = cargo_metadata(self.cwd.as_deref());
+    let package = resolve_addon_package(&metadata, &self.workspace.package)
+      .expect("failed to resolve the addon package to instrument");
+    let package_id = package.id.repr.clone();
+
+    let mut cmd = Command::new("cargo");
+    cmd
+      .arg("build")
+      .arg("--message-format=json-render-diagnostics");
+
+    if let Some(cwd) = &self.cwd {
+      trace!("set cargo working dir to {}", cwd.display());
+      cmd.current_dir(cwd);
+    }
+
+    let target = self
+      .target
+      .clone()
+      .unwrap_or_else(get_system_default_target);
+    cmd.arg("--target").arg(&target);
+
+    if self.features.all_features {
+      cmd.arg("--all-features");
+    } else if self.features.no_default_features {
+      cmd.arg("--no-default-features");
+    } else if !self.features.features.is_empty() {
+      cmd.arg("--features").args(&self.features.features);
+    }
+
+    if self.workspace.all || self.workspace.workspace {
+      cmd.arg("--workspace");
+    } else if !self.workspace.package.is_empty() {
+      cmd.arg("-p").args(&self.workspace.package);
+    }
+
+    cmd.env("RUSTFLAGS", "-Cinstrument-coverage");
+    cmd.env(
+      "LLVM_PROFILE_FILE",
+      profraw_dir.join("napi-%p-%m.profraw"),
+    );
+
+    cmd.stdout(Stdio::piped());
+
+    let mut child = cmd.spawn().expect("failed to execute `cargo build`");
+    let stdout = child.stdout.take().expect("failed to capture cargo stdout");
+
+    let mut artifact = None;
+    for line in BufReader::new(stdout).lines() {
+      let line = match line {
+        Ok(line) => line,
+        Err(_) => continue,
+      };
+      if let Some(path) = parse_cdylib_artifact(&line, &package_id) {
+        artifact = Some(path);
+      }
+    }
+
+    let status = child.wait().expect("failed to wait on `cargo build`");
+    if !status.success() {
+      std::process::exit(status.code().unwrap_or(1));
+    }
+
+    artifact.expect("`cargo build` did not produce a `cdylib` artifact to instrument")
+  }
+
+  fn test_command_parts(&mut self, profraw_dir: &PathBuf) -> Command {
+    let mut parts = self.test_command.split_whitespace();
+    let program = parts
+      .next()
+      .expect("`--test-command` must not be empty");
+
+    let mut cmd = Command::new(program);
+    cmd.args(parts);
+    cmd.env("LLVM_PROFILE_FILE", profraw_dir.join("napi-%p-%m.profraw"));
+
+    if let Some(cwd) = &self.cwd {
+      cmd.current_dir(cwd);
+    }
+
+    cmd
+  }
+}
+
+fn merge_profiles(profraw_dir: &PathBuf, merged_profdata: &PathBuf) {
+  trace!("merging profraw files in {}", profraw_dir.display());
+
+  let profraw_files: Vec<PathBuf> = std::fs::read_dir(profraw_dir)
+    .expect("failed to read coverage temp dir")
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("profraw"))
+    .collect();
+
+  let status = Command::new("llvm-profdata")
+    .arg("merge")
+    .arg("-sparse")
+    .args(&profraw_files)
+    .arg("-o")
+    .arg(merged_profdata)
+    .status()
+    .expect("failed to execute `llvm-profdata`, is it installed?");
+
+  if !status.success() {
+    std::process::exit(status.code().unwrap_or(1));
+  }
+}
+
+fn export_lcov(merged_profdata: &PathBuf, artifact: &PathBuf, output: &PathBuf) {
+  trace!("exporting lcov report to {}", output.display());
+  let lcov = Command::new("llvm-cov")
+    .arg("export")
+    .arg("--instr-profile")
+    .arg(merged_profdata)
+    .arg("--format=lcov")
+    .arg(artifact)
+    .output()
+    .expect("failed to execute `llvm-cov`, is it installed?");
+
+  if !lcov.status.success() {
+    std::process::exit(lcov.status.code().unwrap_or(1));
+  }
+
+  std::fs::write(output, lcov.stdout).expect("failed to write lcov report");
+}
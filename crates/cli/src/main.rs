@@ -1,7 +1,9 @@
 use clap::{Parser, Subcommand};
 use util::Executable;
 
+mod audit;
 mod build;
+mod coverage;
 mod new;
 mod util;
 
@@ -16,6 +18,8 @@ struct Cli {
 enum SubCommand {
   New(new::NewCommand),
   Build(build::BuildCommand),
+  Coverage(coverage::CoverageCommand),
+  Audit(audit::AuditCommand),
 }
 
 macro_rules! run_command {
@@ -41,5 +45,5 @@ fn main() {
   if log::set_boxed_logger(Box::new(util::SimpleLogger)).is_err() {}
   log::set_max_level(log::LevelFilter::Info);
 
-  run_command!(cli.command, New, Build);
+  run_command!(cli.command, New, Build, Coverage, Audit);
 }
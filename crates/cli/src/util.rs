@@ -1,11 +1,13 @@
 mod executable;
 mod fs;
 mod logger;
+mod metadata;
 mod require;
 mod target;
 
 pub use executable::*;
 pub use fs::*;
 pub use logger::*;
+pub use metadata::*;
 pub use require::*;
 pub use target::*;
@@ -0,0 +1,87 @@
+use cargo_metadata::{Metadata, MetadataCommand, Package};
+use log::trace;
+use std::path::Path;
+
+/// Runs `cargo metadata --format-version=1 --no-deps` in `cwd` (or the current directory) and
+/// returns the parsed metadata.
+pub fn cargo_metadata(cwd: Option<&Path>) -> Metadata {
+  let mut cmd = MetadataCommand::new();
+  cmd.no_deps();
+
+  if let Some(cwd) = cwd {
+    cmd.current_dir(cwd);
+  }
+
+  trace!("running `cargo metadata` in {:?}", cwd);
+  cmd.exec().expect("failed to execute `cargo metadata`")
+}
+
+/// Runs `cargo metadata --format-version=1`, resolving the full dependency graph, in `cwd`
+/// (or the current directory) and returns the parsed metadata.
+pub fn cargo_metadata_resolved(cwd: Option<&Path>) -> Metadata {
+  let mut cmd = MetadataCommand::new();
+
+  if let Some(cwd) = cwd {
+    cmd.current_dir(cwd);
+  }
+
+  trace!("running `cargo metadata` (resolving dependencies) in {:?}", cwd);
+  cmd.exec().expect("failed to execute `cargo metadata`")
+}
+
+/// Returns every package in `metadata` that declares a `cdylib` target.
+pub fn cdylib_packages(metadata: &Metadata) -> Vec<&Package> {
+  metadata
+    .packages
+    .iter()
+    .filter(|package| {
+      package
+        .targets
+        .iter()
+        .any(|target| target.kind.iter().any(|kind| kind == "cdylib"))
+    })
+    .collect()
+}
+
+/// Resolves the single `cdylib` addon package to build, optionally narrowed down by an
+/// explicit `-p`/`--package` selection. Logs a clear error and returns `Err(())` when no
+/// `cdylib` target exists, or when more than one addon crate is present and none was selected.
+pub fn resolve_addon_package<'a>(
+  metadata: &'a Metadata,
+  selected: &[String],
+) -> Result<&'a Package, ()> {
+  let candidates = cdylib_packages(metadata);
+
+  if !selected.is_empty() {
+    return candidates
+      .into_iter()
+      .find(|package| selected.contains(&package.name))
+      .ok_or_else(|| {
+        log::error!(
+          "none of the selected packages ({}) declare a `cdylib` target",
+          selected.join(", ")
+        );
+      });
+  }
+
+  match candidates.len() {
+    0 => {
+      log::error!(
+        "no package with a `cdylib` target was found, make sure `crate-type = [\"cdylib\"]` is set under `[lib]`"
+      );
+      Err(())
+    }
+    1 => Ok(candidates[0]),
+    _ => {
+      log::error!(
+        "multiple `cdylib` packages found ({}), select one with `-p`",
+        candidates
+          .iter()
+          .map(|package| package.name.as_str())
+          .collect::<Vec<_>>()
+          .join(", ")
+      );
+      Err(())
+    }
+  }
+}